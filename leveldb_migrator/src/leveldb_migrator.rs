@@ -5,46 +5,103 @@ use leveldb::database::iterator::Iterable;
 use leveldb::db::Database;
 use leveldb::options::Options;
 use leveldb::options::ReadOptions;
-use tracing::{info, Level};
+use reth_db::{
+    database::Database as RethDatabase,
+    mdbx::{Env, EnvKind, WriteMap},
+    models::StoredBlockBodyIndices,
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_primitives::{Header, TransactionSigned, H256};
+use rlp::Rlp;
+use tracing::{info, warn, Level};
 use tracing_subscriber::FmtSubscriber;
 
 // https://github.com/ethereum/go-ethereum/blob/master/core/rawdb/schema.go
 
 /// Byte prefix for header keys
 /// HEADER_PREFIX ++ number (uint64 big endian) + hash -> header
-#[allow(dead_code)]
 static HEADER_PREFIX: &[u8] = b"h";
 
 /// Byte prefix for block body keys
 /// BODY_PREFIX ++ number (uint64 big endian) + hash -> body
-#[allow(dead_code)]
 static BODY_PREFIX: &[u8] = b"b";
 
 /// Byte prefix for transaction lookup keys
 /// TRANSACTION_PREFIX ++ hash -> transaction / receipt lookup metadata
-#[allow(dead_code)]
 static TX_LOOKUP_PREFIX: &[u8] = b"l";
 
 /// Account trie prefix
 /// ACCOUNT_TRIE_PREFIX ++ hexPath -> trie node
 /// TODO: Do we want the trie node, or the trie node value? If we want the value, b"a"
-#[allow(dead_code)]
 static ACCOUNT_TRIE_PREFIX: &[u8] = b"A";
 
 /// Storage trie prefix
 /// STORAGE_TRIE_PREFIX ++ accountHash ++ hexPath -> trie node
 /// TODO: Do we want the trie node, or the trie node value? If we want the value, b"o"
-#[allow(dead_code)]
 static STORAGE_TRIE_PREFIX: &[u8] = b"O";
 
+/// `'h' ++ number(8) ++ hash(32)`
+const HEADER_KEY_LEN: usize = 1 + 8 + 32;
+/// `'b' ++ number(8) ++ hash(32)`
+const BODY_KEY_LEN: usize = 1 + 8 + 32;
+/// `'l' ++ hash(32)`
+const TX_LOOKUP_KEY_LEN: usize = 1 + 32;
+
+/// Number of leveldb entries processed between MDBX transaction commits. Keeps a single
+/// transaction from holding every migrated row in memory at once.
+const BATCH_SIZE: usize = 10_000;
+
+/// Tally of rows written (or skipped) per destination table, reported once iteration
+/// over the source database completes.
+#[derive(Debug, Default)]
+struct MigrationStats {
+    headers: u64,
+    header_numbers: u64,
+    bodies: u64,
+    transactions: u64,
+    tx_lookups: u64,
+    skipped_tries: u64,
+    undecoded: u64,
+    duplicate_headers: u64,
+    duplicate_bodies: u64,
+}
+
+impl MigrationStats {
+    fn log_summary(&self) {
+        info!(
+            headers = self.headers,
+            header_numbers = self.header_numbers,
+            bodies = self.bodies,
+            transactions = self.transactions,
+            tx_lookups = self.tx_lookups,
+            skipped_tries = self.skipped_tries,
+            undecoded = self.undecoded,
+            duplicate_headers = self.duplicate_headers,
+            duplicate_bodies = self.duplicate_bodies,
+            "Migration summary"
+        );
+        if self.duplicate_headers > 0 || self.duplicate_bodies > 0 {
+            warn!(
+                duplicate_headers = self.duplicate_headers,
+                duplicate_bodies = self.duplicate_bodies,
+                "source leveldb had non-canonical headers/bodies sharing a block number with a \
+                 canonical entry; only the last one iterated (by hash) survived the migration"
+            );
+        }
+    }
+}
+
 fn main() -> Result<()> {
     // Setup tracing
     setup_tracing()?;
 
-    // Designate the path to the leveldb database
+    // Designate the path to the leveldb database and the destination MDBX environment
     let args: Vec<String> = std::env::args().collect();
-    let db_path_buf = PathBuf::from(args.get(1).unwrap());
+    let db_path_buf = PathBuf::from(args.get(1).ok_or_else(|| eyre!("missing leveldb path"))?);
     let db_path = db_path_buf.as_path();
+    let mdbx_path_buf =
+        PathBuf::from(args.get(2).ok_or_else(|| eyre!("missing destination mdbx path"))?);
 
     info!("Opening database at path {:?}", db_path_buf);
 
@@ -53,21 +110,176 @@ fn main() -> Result<()> {
     options.create_if_missing = false;
     let db = Database::open(db_path, &options)?;
 
+    info!("Opening destination MDBX environment at path {:?}", mdbx_path_buf);
+    let mdbx_env: Env<WriteMap> = Env::open(mdbx_path_buf.as_path(), EnvKind::RW)?;
+    mdbx_env.create_tables()?;
+
     info!("Opened leveldb database! Iterating...");
 
-    // Walk the DB and look for keys with the prefixes we want. If we find an entry with a desired prefix,
-    // we need to deserialize the value based on the prefix and convert it to a newly defined rust type.
-    // We can then re-serialize each type into MDBX compatible data for insertion into the new database.
+    // Walk the DB and look for keys with the prefixes we want. If we find an entry with a desired
+    // prefix, deserialize the value based on the prefix and write the translated reth type into
+    // the matching MDBX table. Commit every `BATCH_SIZE` entries to bound memory usage.
+    let mut stats = MigrationStats::default();
+    let mut mdbx_tx = mdbx_env.tx_mut()?;
+    let mut pending = 0usize;
+
     for (key, value) in db.iter(&ReadOptions::new()) {
-        // TODO
-        tracing::debug!("key: {:?} | value: {:?}", key, value);
+        if let Err(error) = migrate_entry(&key, &value, &mdbx_tx, &mut stats) {
+            warn!(?error, key = ?key, "Failed to decode entry, skipping");
+            stats.undecoded += 1;
+        }
+
+        pending += 1;
+        if pending >= BATCH_SIZE {
+            mdbx_tx.commit()?;
+            mdbx_tx = mdbx_env.tx_mut()?;
+            pending = 0;
+        }
+    }
+    mdbx_tx.commit()?;
+
+    info!("Finished iterating! Migration complete.");
+    stats.log_summary();
+
+    Ok(())
+}
+
+/// Dispatches a single leveldb entry to the decoder matching its key prefix, writing the
+/// translated row(s) into `mdbx_tx`.
+fn migrate_entry(
+    key: &[u8],
+    value: &[u8],
+    mdbx_tx: &(impl DbTxMut<WriteMap> + DbTx<WriteMap>),
+    stats: &mut MigrationStats,
+) -> Result<()> {
+    if key.starts_with(HEADER_PREFIX) && key.len() == HEADER_KEY_LEN {
+        insert_header(key, value, mdbx_tx, stats)
+    } else if key.starts_with(BODY_PREFIX) && key.len() == BODY_KEY_LEN {
+        insert_body(key, value, mdbx_tx, stats)
+    } else if key.starts_with(TX_LOOKUP_PREFIX) && key.len() == TX_LOOKUP_KEY_LEN {
+        insert_tx_lookup(key, value, mdbx_tx, stats)
+    } else if key.starts_with(ACCOUNT_TRIE_PREFIX) || key.starts_with(STORAGE_TRIE_PREFIX) {
+        stats.skipped_tries += 1;
+        Ok(())
+    } else {
+        // Not one of the prefixes we migrate (e.g. geth metadata keys); ignore silently.
+        Ok(())
+    }
+}
+
+/// Decodes a `'h' ++ number ++ hash -> header` entry and writes it into the `Headers` and
+/// `HeaderNumbers` tables.
+///
+/// leveldb has no canonical-hash marker consulted here, so if the source database holds a
+/// non-canonical header sharing `number` with the canonical one (e.g. after a reorg), whichever
+/// is iterated later lexicographically by `hash` wins. We can't tell which one that was without
+/// a second pass over the canonical-hash key, so at minimum this warns (and counts the duplicate)
+/// instead of overwriting silently.
+fn insert_header(
+    key: &[u8],
+    value: &[u8],
+    mdbx_tx: &(impl DbTxMut<WriteMap> + DbTx<WriteMap>),
+    stats: &mut MigrationStats,
+) -> Result<()> {
+    let number = u64::from_be_bytes(key[1..9].try_into()?);
+    let hash = H256::from_slice(&key[9..41]);
+
+    let header: Header =
+        rlp::decode(value).map_err(|e| eyre!("failed to decode header {number}: {e}"))?;
+
+    if mdbx_tx.get::<tables::Headers>(number)?.is_some() {
+        warn!(number, ?hash, "overwriting a header already migrated for this block number, is the source leveldb missing a canonical entry?");
+        stats.duplicate_headers += 1;
+    }
+
+    mdbx_tx.put::<tables::Headers>(number, header)?;
+    mdbx_tx.put::<tables::HeaderNumbers>(hash, number)?;
+
+    stats.headers += 1;
+    stats.header_numbers += 1;
+    Ok(())
+}
+
+/// Decodes a `'b' ++ number ++ hash -> body` entry (an RLP list of `[transactions, uncles]`)
+/// and writes each transaction into the `Transactions`/`TxHashNumber` tables along with the
+/// block's `BlockBodyIndices`.
+///
+/// Same caveat as [`insert_header`]: a non-canonical body sharing `number` with the canonical one
+/// overwrites `BlockBodyIndices` for that number (and misaligns the sequential tx
+/// numbering this function hands out). This only warns; [`insert_tx_lookup`] is what actually
+/// catches the corruption when an orphaned `'l'` entry exists for the overwritten transactions.
+fn insert_body(
+    key: &[u8],
+    value: &[u8],
+    mdbx_tx: &(impl DbTxMut<WriteMap> + DbTx<WriteMap>),
+    stats: &mut MigrationStats,
+) -> Result<()> {
+    let number = u64::from_be_bytes(key[1..9].try_into()?);
+
+    if mdbx_tx.get::<tables::BlockBodyIndices>(number)?.is_some() {
+        warn!(number, "overwriting body indices already migrated for this block number, is the source leveldb missing a canonical entry?");
+        stats.duplicate_bodies += 1;
     }
 
-    info!("Finished iterating! Writing dump file...");
+    let rlp = Rlp::new(value);
+    let transactions: Vec<TransactionSigned> = rlp
+        .at(0)
+        .map_err(|e| eyre!("failed to decode body {number}: {e}"))?
+        .as_list()?;
+    // Uncles are intentionally dropped: bedrock and later OP blocks never have any.
 
-    // TODO: Open reth's MDBX database
-    // TODO: Insert serialized reth data into MDBX database
+    let first_tx_num = stats.transactions;
+    for transaction in &transactions {
+        let tx_hash = transaction.hash();
+        mdbx_tx.put::<tables::Transactions>(stats.transactions, transaction.clone())?;
+        mdbx_tx.put::<tables::TxHashNumber>(tx_hash, stats.transactions)?;
+        stats.transactions += 1;
+    }
+
+    mdbx_tx.put::<tables::BlockBodyIndices>(
+        number,
+        StoredBlockBodyIndices { first_tx_num, tx_count: transactions.len() as u64 },
+    )?;
+
+    stats.bodies += 1;
+    Ok(())
+}
+
+/// Decodes a `'l' ++ txhash -> block number` lookup entry and cross-checks it against the
+/// `TxHashNumber`/`BlockBodyIndices` rows [`insert_body`] wrote for that hash. leveldb orders
+/// keys lexicographically, so every `'b'` entry is visited (and its transactions written) before
+/// any `'l'` entry; that ordering is what makes this check possible, rather than just assumed.
+/// Without it, a forked/non-canonical body sharing a block number with the canonical one would
+/// silently misalign `insert_body`'s sequential tx numbering with no error raised anywhere.
+fn insert_tx_lookup(
+    key: &[u8],
+    value: &[u8],
+    mdbx_tx: &(impl DbTxMut<WriteMap> + DbTx<WriteMap>),
+    stats: &mut MigrationStats,
+) -> Result<()> {
+    let tx_hash = H256::from_slice(&key[1..33]);
+    let block_number: u64 = rlp::decode(value)?;
+
+    let tx_number = mdbx_tx
+        .get::<tables::TxHashNumber>(tx_hash)?
+        .ok_or_else(|| eyre!("tx lookup entry for {tx_hash:?} has no TxHashNumber row"))?;
+
+    let body = mdbx_tx
+        .get::<tables::BlockBodyIndices>(block_number)?
+        .ok_or_else(|| {
+            eyre!("tx lookup entry for {tx_hash:?} references unknown block {block_number}")
+        })?;
+
+    if !(body.first_tx_num..body.first_tx_num + body.tx_count).contains(&tx_number) {
+        return Err(eyre!(
+            "tx lookup entry for {tx_hash:?} claims block {block_number}, but its TxHashNumber \
+             ({tx_number}) falls outside that block's tx range [{}, {})",
+            body.first_tx_num,
+            body.first_tx_num + body.tx_count
+        ))
+    }
 
+    stats.tx_lookups += 1;
     Ok(())
 }
 
@@ -0,0 +1,298 @@
+use std::collections::BTreeMap;
+
+use clap::Parser;
+use eyre::{eyre, Result};
+use reth::runner::CliContext;
+use reth_db::{
+    database::Database as RethDatabase,
+    mdbx::{Env, EnvKind, WriteMap},
+    tables,
+    transaction::DbTx,
+};
+use reth_primitives::{TransactionSigned, U256};
+use serde::Serialize;
+
+use crate::cli::{
+    genesis::{chain_value_parser, OpChainSpec},
+    receipts::Receipt,
+};
+
+/// Fee-history command
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The path to the reth MDBX environment populated by the leveldb migrator, used to look up
+    /// headers, bodies and transactions for the requested range
+    #[arg(long, value_name = "DB", verbatim_doc_comment)]
+    db: String,
+
+    /// The path to the receipts export (see the `receipts` command), used for the L1 fee
+    /// components that aren't part of a block's transactions
+    #[arg(long, value_name = "RECEIPTS", verbatim_doc_comment, default_value = "data/")]
+    receipts: String,
+
+    /// Number of blocks in the requested range
+    #[arg(long, value_name = "COUNT")]
+    block_count: u64,
+
+    /// Highest block number in the requested range
+    #[arg(long, value_name = "BLOCK")]
+    newest_block: u64,
+
+    /// Reward percentiles (0-100) to report for each block
+    #[arg(long, value_name = "PERCENTILES", value_delimiter = ',')]
+    reward_percentiles: Vec<f64>,
+
+    /// The chain whose EIP-1559 parameters (elasticity, denominator) drive the projected
+    /// next-block base fee
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        verbatim_doc_comment,
+        default_value = "mainnet",
+        value_parser = chain_value_parser
+    )]
+    chain: OpChainSpec,
+}
+
+impl Command {
+    /// Execute the command
+    pub async fn execute(self, _ctx: CliContext) -> eyre::Result<()> {
+        tracing::info!(target: "op-reth::fee_history", newest_block = self.newest_block, block_count = self.block_count, "computing fee history");
+
+        let receipts = Receipt::from_file(&self.receipts)?;
+        let env: Env<WriteMap> = Env::open(std::path::Path::new(&self.db), EnvKind::RO)?;
+        let tx = env.tx()?;
+
+        let result = fee_history(
+            &tx,
+            &receipts,
+            self.block_count,
+            self.newest_block,
+            &self.reward_percentiles,
+            self.chain.eip1559_elasticity,
+            self.chain.eip1559_denominator,
+        )?;
+
+        println!("{}", serde_json::to_string_pretty(&result)?);
+
+        Ok(())
+    }
+}
+
+/// Response to an `eth_feeHistory`-style query, extended with OP L1 fee components.
+#[derive(Debug, Clone, Serialize)]
+pub struct FeeHistoryResult {
+    /// The lowest block number in the returned range
+    pub oldest_block: u64,
+    /// `base_fee_per_gas` for each block in the range, plus one extra entry for the projected
+    /// base fee of the block after `newest_block`
+    pub base_fee_per_gas: Vec<U256>,
+    /// `cumulative_gas_used` of a block's last receipt divided by its gas limit
+    pub gas_used_ratio: Vec<f64>,
+    /// Gas-weighted effective priority fee for each requested percentile, per block
+    pub reward: Vec<Vec<U256>>,
+    /// Sum of `l1_fee` across a block's receipts
+    pub l1_fee_per_block: Vec<U256>,
+    /// Average `l1_fee_scalar` across every receipt in the range
+    pub l1_fee_scalar_avg: f64,
+}
+
+/// Computes an [`FeeHistoryResult`] for `[newest_block - block_count + 1, newest_block]` from
+/// headers/bodies/transactions stored in `tx` and L1 fee data carried by `receipts`.
+fn fee_history(
+    tx: &impl DbTx<WriteMap>,
+    receipts: &[Receipt],
+    block_count: u64,
+    newest_block: u64,
+    reward_percentiles: &[f64],
+    elasticity: u64,
+    denominator: u64,
+) -> Result<FeeHistoryResult> {
+    if block_count == 0 {
+        return Err(eyre!("block_count must be greater than zero"))
+    }
+    let oldest_block = newest_block.saturating_sub(block_count - 1);
+
+    let mut receipts_by_block: BTreeMap<u64, Vec<&Receipt>> = BTreeMap::new();
+    for receipt in receipts {
+        receipts_by_block.entry(receipt.block_number.as_u64()).or_default().push(receipt);
+    }
+    for block_receipts in receipts_by_block.values_mut() {
+        block_receipts.sort_by_key(|r| r.transaction_index);
+    }
+
+    let mut base_fee_per_gas = Vec::with_capacity(block_count as usize + 1);
+    let mut gas_used_ratio = Vec::with_capacity(block_count as usize);
+    let mut reward = Vec::with_capacity(block_count as usize);
+    let mut l1_fee_per_block = Vec::with_capacity(block_count as usize);
+    let mut l1_fee_scalar_sum = 0f64;
+    let mut l1_fee_scalar_count = 0u64;
+    let mut last: Option<(u64, u64, u64)> = None; // (base_fee, gas_limit, gas_used) of the newest block processed
+
+    for number in oldest_block..=newest_block {
+        let header = tx
+            .get::<tables::Headers>(number)?
+            .ok_or_else(|| eyre!("missing header for block {number}"))?;
+        let body = tx
+            .get::<tables::BlockBodyIndices>(number)?
+            .ok_or_else(|| eyre!("missing body indices for block {number}"))?;
+
+        let base_fee = header.base_fee_per_gas.unwrap_or_default();
+        base_fee_per_gas.push(U256::from(base_fee));
+
+        let block_receipts = receipts_by_block.get(&number).cloned().unwrap_or_default();
+        let gas_used = block_receipts.last().map(|r| r.cumulative_gas_used).unwrap_or_default();
+        gas_used_ratio.push(gas_used as f64 / header.gas_limit as f64);
+
+        let mut l1_fee_block = U256::ZERO;
+        for receipt in &block_receipts {
+            l1_fee_block += receipt.l1_fee;
+            if let Ok(scalar) = receipt.l1_fee_scalar.parse::<f64>() {
+                l1_fee_scalar_sum += scalar;
+                l1_fee_scalar_count += 1;
+            }
+        }
+        l1_fee_per_block.push(l1_fee_block);
+
+        let mut transactions = Vec::with_capacity(body.tx_count as usize);
+        for tx_num in body.first_tx_num..body.first_tx_num + body.tx_count {
+            let transaction = tx
+                .get::<tables::Transactions>(tx_num)?
+                .ok_or_else(|| eyre!("missing transaction {tx_num} for block {number}"))?;
+            transactions.push(transaction);
+        }
+
+        reward.push(block_rewards(
+            &transactions,
+            &block_receipts,
+            base_fee,
+            gas_used,
+            reward_percentiles,
+        )?);
+
+        last = Some((base_fee, header.gas_limit, gas_used));
+    }
+
+    if let Some((base_fee, gas_limit, gas_used)) = last {
+        base_fee_per_gas.push(U256::from(next_base_fee_per_gas(
+            base_fee, gas_used, gas_limit, elasticity, denominator,
+        )));
+    }
+
+    let l1_fee_scalar_avg =
+        if l1_fee_scalar_count > 0 { l1_fee_scalar_sum / l1_fee_scalar_count as f64 } else { 0.0 };
+
+    Ok(FeeHistoryResult {
+        oldest_block,
+        base_fee_per_gas,
+        gas_used_ratio,
+        reward,
+        l1_fee_per_block,
+        l1_fee_scalar_avg,
+    })
+}
+
+/// Computes the gas-weighted effective priority fee for each percentile in `percentiles`: sort
+/// the block's transactions ascending by effective tip, walk cumulative gas used by tx until it
+/// crosses `percentile / 100 * gas_used`, and take that transaction's tip.
+fn block_rewards(
+    transactions: &[TransactionSigned],
+    receipts: &[&Receipt],
+    base_fee: u64,
+    gas_used: u64,
+    percentiles: &[f64],
+) -> Result<Vec<U256>> {
+    if percentiles.is_empty() || transactions.is_empty() {
+        return Ok(vec![U256::ZERO; percentiles.len()])
+    }
+
+    let mut prev_cumulative = 0u64;
+    let mut weighted_tips = Vec::with_capacity(transactions.len());
+    for (transaction, receipt) in transactions.iter().zip(receipts.iter()) {
+        let tx_gas_used = receipt.cumulative_gas_used.saturating_sub(prev_cumulative);
+        prev_cumulative = receipt.cumulative_gas_used;
+
+        let tip = transaction.effective_gas_tip(Some(base_fee as u128)).unwrap_or_default();
+        weighted_tips.push((U256::from(tip), tx_gas_used));
+    }
+    weighted_tips.sort_by_key(|(tip, _)| *tip);
+
+    let mut rewards = Vec::with_capacity(percentiles.len());
+    for percentile in percentiles {
+        let target = (*percentile / 100.0) * gas_used as f64;
+        let mut cumulative = 0u64;
+        let mut reward = weighted_tips.last().map(|(tip, _)| *tip).unwrap_or_default();
+        for (tip, tx_gas_used) in &weighted_tips {
+            cumulative += tx_gas_used;
+            if cumulative as f64 >= target {
+                reward = *tip;
+                break
+            }
+        }
+        rewards.push(reward);
+    }
+
+    Ok(rewards)
+}
+
+/// Computes the next block's `base_fee_per_gas` per EIP-1559, parameterized by OP's elasticity
+/// multiplier and base fee max change denominator rather than Ethereum's 2x/8 defaults.
+fn next_base_fee_per_gas(
+    base_fee: u64,
+    gas_used: u64,
+    gas_limit: u64,
+    elasticity: u64,
+    denominator: u64,
+) -> u64 {
+    let gas_target = gas_limit / elasticity.max(1);
+
+    if gas_used == gas_target {
+        return base_fee
+    }
+
+    if gas_used > gas_target {
+        let gas_used_delta = gas_used - gas_target;
+        let base_fee_delta =
+            (base_fee as u128 * gas_used_delta as u128 / gas_target.max(1) as u128
+                / denominator.max(1) as u128)
+                .max(1) as u64;
+        base_fee + base_fee_delta
+    } else {
+        let gas_used_delta = gas_target - gas_used;
+        let base_fee_delta = base_fee as u128 * gas_used_delta as u128
+            / gas_target.max(1) as u128
+            / denominator.max(1) as u128;
+        base_fee.saturating_sub(base_fee_delta as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::next_base_fee_per_gas;
+
+    #[test]
+    fn holds_steady_at_target_gas_used() {
+        assert_eq!(next_base_fee_per_gas(1_000_000_000, 15_000_000, 30_000_000, 2, 8), 1_000_000_000);
+    }
+
+    #[test]
+    fn rises_when_gas_used_exceeds_target() {
+        assert!(next_base_fee_per_gas(1_000_000_000, 30_000_000, 30_000_000, 2, 8) > 1_000_000_000);
+    }
+
+    #[test]
+    fn falls_when_gas_used_is_below_target() {
+        assert!(next_base_fee_per_gas(1_000_000_000, 0, 30_000_000, 2, 8) < 1_000_000_000);
+    }
+
+    #[test]
+    fn distinguishes_gas_used_values_that_a_lossy_float_round_trip_would_conflate() {
+        // Regression test for 335a025: `fee_history` used to re-derive `gas_used` from
+        // `gas_used_ratio` via an f64 round trip before calling this function, which could
+        // collapse distinct gas_used values onto the same projected base fee. Now that the exact
+        // integer is threaded through, nearby values must project to different fees.
+        let a = next_base_fee_per_gas(1_000_000_000, 10_000_001, 30_000_000, 2, 8);
+        let b = next_base_fee_per_gas(1_000_000_000, 10_000_000, 30_000_000, 2, 8);
+        assert_ne!(a, b);
+    }
+}
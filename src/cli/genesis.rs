@@ -1,11 +1,37 @@
 use std::{collections::HashMap, fs::File, io::BufReader, path::Path};
 
 use clap::{crate_version, Parser};
-use eyre::Result;
+use eyre::{eyre, Result};
 use reth::runner::CliContext;
-use reth_primitives::{Address, GenesisAccount};
+use reth_primitives::{
+    Address, Bytes, Chain, ChainSpec, ChainSpecBuilder, ForkCondition, Genesis as RethGenesis,
+    GenesisAccount, Hardfork, H256, U256,
+};
+use reth_staged_sync::utils::chainspec::genesis_value_parser;
 use serde::{Deserialize, Serialize};
 
+/// Ethereum mainnet's default EIP-1559 elasticity multiplier, used as a fallback for genesis
+/// files that don't carry an `optimism` override.
+const DEFAULT_EIP1559_ELASTICITY: u64 = 2;
+/// Ethereum mainnet's default EIP-1559 base fee max change denominator, used as a fallback for
+/// genesis files that don't carry an `optimism` override.
+const DEFAULT_EIP1559_DENOMINATOR: u64 = 8;
+
+/// Parses `--chain`: either a named chain / bare `ChainSpec` JSON (handled by
+/// [`genesis_value_parser`]), or a path to an Optimism genesis file (`bedrockBlock`,
+/// `optimism.eip1559Elasticity`, etc).
+pub fn chain_value_parser(s: &str) -> eyre::Result<OpChainSpec> {
+    if let Ok(genesis) = Genesis::from_file(s) {
+        return genesis.into_chain_spec()
+    }
+
+    Ok(OpChainSpec {
+        inner: genesis_value_parser(s)?,
+        eip1559_elasticity: DEFAULT_EIP1559_ELASTICITY,
+        eip1559_denominator: DEFAULT_EIP1559_DENOMINATOR,
+    })
+}
+
 /// Genesis command
 #[derive(Debug, Parser)]
 pub struct Command {
@@ -20,14 +46,33 @@ impl Command {
         tracing::info!(target: "op-reth::genesis", "loading genesis file {}", crate_version!());
 
         let genesis = Genesis::from_file(self.path)?;
-        println!("Genesis: {:#?}", genesis);
-
         tracing::debug!(target: "op-reth::genesis", genesis = ?genesis, "genesis file loaded");
 
+        let chain_spec = genesis.into_chain_spec()?;
+        println!("ChainSpec: {:#?}", chain_spec.inner);
+        println!(
+            "EIP-1559 params: elasticity={} denominator={}",
+            chain_spec.eip1559_elasticity, chain_spec.eip1559_denominator
+        );
+
         Ok(())
     }
 }
 
+/// A [`ChainSpec`] paired with the EIP-1559 parameters carried by an Optimism genesis file.
+///
+/// OP chains override Ethereum's default elasticity multiplier (2) and base fee max change
+/// denominator (8), so base-fee math needs access to them alongside the regular chain spec.
+#[derive(Debug, Clone)]
+pub struct OpChainSpec {
+    /// The underlying Ethereum chain spec: hardfork schedule and genesis state.
+    pub inner: ChainSpec,
+    /// EIP-1559 elasticity multiplier for this chain.
+    pub eip1559_elasticity: u64,
+    /// EIP-1559 base fee max change denominator for this chain.
+    pub eip1559_denominator: u64,
+}
+
 /// Optimism Object
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Optimism {
@@ -87,10 +132,19 @@ pub struct GenesisConfig {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Genesis {
     pub config: GenesisConfig,
+    #[serde(default)]
+    pub nonce: Option<String>,
+    #[serde(default)]
+    pub timestamp: Option<String>,
     pub difficulty: String,
     #[serde(rename = "gasLimit")]
     pub gas_limit: String,
+    #[serde(rename = "extraData")]
     pub extradata: String,
+    #[serde(default, rename = "mixHash")]
+    pub mix_hash: Option<String>,
+    #[serde(default)]
+    pub coinbase: Option<String>,
     pub alloc: HashMap<Address, GenesisAccount>,
 }
 
@@ -100,4 +154,94 @@ impl Genesis {
         let reader = BufReader::new(file);
         Ok(serde_json::from_reader(reader)?)
     }
+
+    /// Builds a [`OpChainSpec`] out of this genesis file: the fork activation schedule, the
+    /// terminal total difficulty, the genesis allocation, and OP's EIP-1559 parameters.
+    pub fn into_chain_spec(self) -> Result<OpChainSpec> {
+        let config = &self.config;
+
+        let mut builder = ChainSpecBuilder::default().chain(Chain::from(config.chain_id));
+
+        for (hardfork, block) in [
+            (Hardfork::Homestead, config.homestead_block),
+            (Hardfork::Tangerine, config.eip150_block),
+            // eip155Block and eip158Block activate together as "Spurious Dragon"
+            (Hardfork::SpuriousDragon, config.eip155_block),
+            (Hardfork::Byzantium, config.byzantium_block),
+            (Hardfork::Constantinople, config.constantinople_block),
+            (Hardfork::Petersburg, config.petersburg_block),
+            (Hardfork::Istanbul, config.istanbul_block),
+            (Hardfork::MuirGlacier, config.muir_glacier_block),
+            (Hardfork::Berlin, config.berlin_block),
+            (Hardfork::London, config.london_block),
+            (Hardfork::ArrowGlacier, config.arrow_glacier_block),
+            (Hardfork::GrayGlacier, config.gray_glacier_block),
+            (Hardfork::Bedrock, config.bedrock_block),
+        ] {
+            if block != 0 {
+                builder = builder.with_fork(hardfork, ForkCondition::Block(block));
+            }
+        }
+
+        if config.terminal_total_difficulty_passed || config.terminal_total_difficulty != 0 {
+            builder = builder.with_fork(
+                Hardfork::Paris,
+                ForkCondition::TTD {
+                    fork_block: (config.merge_netsplit_block != 0)
+                        .then_some(config.merge_netsplit_block),
+                    total_difficulty: U256::from(config.terminal_total_difficulty),
+                },
+            );
+        }
+
+        let difficulty = U256::from_str_radix(self.difficulty.trim_start_matches("0x"), 16)
+            .map_err(|e| eyre!("invalid genesis difficulty {:?}: {e}", self.difficulty))?;
+        let gas_limit = u64::from_str_radix(self.gas_limit.trim_start_matches("0x"), 16)
+            .map_err(|e| eyre!("invalid genesis gasLimit {:?}: {e}", self.gas_limit))?;
+        let extra_data = Bytes::from(
+            hex::decode(self.extradata.trim_start_matches("0x"))
+                .map_err(|e| eyre!("invalid genesis extradata {:?}: {e}", self.extradata))?,
+        );
+        let nonce = match &self.nonce {
+            Some(nonce) => u64::from_str_radix(nonce.trim_start_matches("0x"), 16)
+                .map_err(|e| eyre!("invalid genesis nonce {:?}: {e}", nonce))?,
+            None => 0,
+        };
+        let timestamp = match &self.timestamp {
+            Some(timestamp) => u64::from_str_radix(timestamp.trim_start_matches("0x"), 16)
+                .map_err(|e| eyre!("invalid genesis timestamp {:?}: {e}", timestamp))?,
+            None => 0,
+        };
+        let mix_hash = match &self.mix_hash {
+            Some(mix_hash) => {
+                mix_hash.parse().map_err(|e| eyre!("invalid genesis mixHash {:?}: {e}", mix_hash))?
+            }
+            None => H256::zero(),
+        };
+        let coinbase = match &self.coinbase {
+            Some(coinbase) => coinbase
+                .parse()
+                .map_err(|e| eyre!("invalid genesis coinbase {:?}: {e}", coinbase))?,
+            None => Address::zero(),
+        };
+
+        let genesis = RethGenesis {
+            nonce,
+            timestamp,
+            extra_data,
+            gas_limit,
+            difficulty,
+            mix_hash,
+            coinbase,
+            alloc: self.alloc,
+        };
+
+        let inner = builder.genesis(genesis).build();
+
+        Ok(OpChainSpec {
+            inner,
+            eip1559_elasticity: config.optimism.eip1559_elasticity,
+            eip1559_denominator: config.optimism.eip1559_denominator,
+        })
+    }
 }
@@ -0,0 +1,124 @@
+use clap::Parser;
+use eyre::Result;
+use reth::runner::CliContext;
+use reth_primitives::{Address, Bloom, Bytes, H256};
+use serde::Serialize;
+
+use crate::cli::receipts::Receipt;
+
+/// Logs command
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The path to the receipts export (see the `receipts` command)
+    #[arg(long, value_name = "RECEIPTS", verbatim_doc_comment, default_value = "data/")]
+    receipts: String,
+
+    /// Only return logs emitted by one of these addresses; matches every address if omitted
+    #[arg(long, value_delimiter = ',')]
+    address: Vec<Address>,
+
+    /// Per-position topic filter, one `--topics` per log position, each a comma-separated list
+    /// of alternatives (OR semantics). Pass an empty string for a position to match any topic.
+    #[arg(long = "topics", value_name = "TOPICS")]
+    topics: Vec<String>,
+
+    /// Lowest block number (inclusive) to scan
+    #[arg(long, default_value_t = 0)]
+    from_block: u64,
+
+    /// Highest block number (inclusive) to scan
+    #[arg(long)]
+    to_block: u64,
+}
+
+/// A single matched log, in roughly `eth_getLogs` response shape.
+#[derive(Debug, Clone, Serialize)]
+pub struct LogEntry {
+    pub block_number: u64,
+    pub transaction_hash: H256,
+    pub log_index: u64,
+    pub address: Address,
+    pub topics: Vec<H256>,
+    pub data: Bytes,
+}
+
+impl Command {
+    /// Execute the command
+    pub async fn execute(self, _ctx: CliContext) -> eyre::Result<()> {
+        let receipts = Receipt::from_file(&self.receipts)?;
+
+        let topics: Vec<Option<Vec<H256>>> = self
+            .topics
+            .iter()
+            .map(|position| {
+                if position.is_empty() {
+                    None
+                } else {
+                    Some(position.split(',').filter_map(|t| t.parse().ok()).collect())
+                }
+            })
+            .collect();
+
+        let mut matches = Vec::new();
+        for receipt in &receipts {
+            let block_number = receipt.block_number.as_u64();
+            if block_number < self.from_block || block_number > self.to_block {
+                continue
+            }
+
+            if !bloom_matches(&receipt.bloom, &self.address, &topics) {
+                continue
+            }
+
+            let (logs, bloom_valid) = receipt.decode_logs()?;
+            if !bloom_valid {
+                tracing::warn!(target: "reth::cli", tx_hash = ?receipt.tx_hash, "decoded logs bloom does not match stored bloom, skipping");
+                continue
+            }
+
+            for (log_index, log) in logs.into_iter().enumerate() {
+                if !self.address.is_empty() && !self.address.contains(&log.address) {
+                    continue
+                }
+                if !topics_match(&log.topics, &topics) {
+                    continue
+                }
+
+                matches.push(LogEntry {
+                    block_number,
+                    transaction_hash: receipt.tx_hash,
+                    log_index: log_index as u64,
+                    address: log.address,
+                    topics: log.topics,
+                    data: log.data,
+                });
+            }
+        }
+
+        println!("{}", serde_json::to_string_pretty(&matches)?);
+        Ok(())
+    }
+}
+
+/// Short-circuits a receipt whose stored bloom can't possibly contain the requested
+/// addresses/topics, without needing to decode its logs.
+fn bloom_matches(bloom: &[u8], address: &[Address], topics: &[Option<Vec<H256>>]) -> bool {
+    let bloom = Bloom::from_slice(bloom);
+
+    if !address.is_empty() && !address.iter().any(|a| bloom.contains_input(a.as_bytes())) {
+        return false
+    }
+
+    topics.iter().all(|position| match position {
+        None => true,
+        Some(options) => options.iter().any(|t| bloom.contains_input(t.as_bytes())),
+    })
+}
+
+/// Applies the per-position OR topic filter to a decoded log's topics.
+fn topics_match(log_topics: &[H256], filter: &[Option<Vec<H256>>]) -> bool {
+    filter.iter().enumerate().all(|(i, options)| match options {
+        None => true,
+        Some(options) => log_topics.get(i).map(|t| options.contains(t)).unwrap_or(false),
+    })
+}
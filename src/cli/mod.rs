@@ -5,8 +5,11 @@ use reth::{
 };
 
 pub mod dirs;
+pub mod fee_history;
 pub mod genesis;
+pub mod logs;
 pub mod receipts;
+pub mod sync;
 
 pub fn run() -> eyre::Result<()> {
     dotenv::dotenv().ok();
@@ -20,6 +23,9 @@ pub fn run() -> eyre::Result<()> {
     match opt.command {
         Commands::Genesis(command) => runner.run_command_until_exit(|ctx| command.execute(ctx)),
         Commands::Receipts(command) => runner.run_command_until_exit(|ctx| command.execute(ctx)),
+        Commands::FeeHistory(command) => runner.run_command_until_exit(|ctx| command.execute(ctx)),
+        Commands::Logs(command) => runner.run_command_until_exit(|ctx| command.execute(ctx)),
+        Commands::Node(command) => runner.run_command_until_exit(|ctx| command.execute(ctx)),
     }
 }
 
@@ -32,6 +38,16 @@ pub enum Commands {
     /// Load Receipts
     #[command(name = "receipts")]
     Receipts(receipts::Command),
+    /// Query an `eth_feeHistory`-style window over imported data
+    #[command(name = "fee-history")]
+    FeeHistory(fee_history::Command),
+    /// Query an `eth_getLogs`-style filter over imported receipts
+    #[command(name = "logs")]
+    Logs(logs::Command),
+    /// Sync headers and state from the network, restoring from (and snapshotting to) a
+    /// configurable remote store
+    #[command(name = "node")]
+    Node(sync::Command),
 }
 
 #[derive(Parser)]
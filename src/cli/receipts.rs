@@ -3,7 +3,15 @@ use std::path::Path;
 use clap::Parser;
 use eyre::Result;
 use reth::runner::CliContext;
-use reth_primitives::{rpc::H256, U256};
+use reth_db::{
+    database::Database as RethDatabase,
+    mdbx::{Env, EnvKind, WriteMap},
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_primitives::{
+    bloom::logs_bloom, rpc::H256, Address, Bytes, Log, Receipt as RethReceipt, TxType, U256,
+};
 use rlp::Decodable;
 use serde::{Deserialize, Serialize};
 
@@ -13,6 +21,12 @@ pub struct Command {
     /// The path to the receipts export
     #[arg(long, value_name = "RECEIPTS", verbatim_doc_comment, default_value = "data/")]
     path: String,
+
+    /// The path to the reth MDBX environment populated by the leveldb migrator. When set, each
+    /// receipt's decoded logs are validated against its stored bloom and persisted into the
+    /// `Receipts` table, keyed by the global transaction number looked up via `BlockBodyIndices`
+    #[arg(long, value_name = "DB", verbatim_doc_comment)]
+    db: Option<String>,
 }
 
 impl Command {
@@ -21,10 +35,46 @@ impl Command {
         tracing::info!(target: "reth::cli", "loading receipts file \"{}\"", self.path);
         let receipts = Receipt::from_file(self.path)?;
         tracing::info!(target: "reth::cli", "got {} receipts", receipts.len());
+
+        if let Some(db) = self.db {
+            let persisted = persist_decoded_receipts(&db, &receipts)?;
+            tracing::info!(target: "reth::cli", persisted, "persisted decoded receipts");
+        }
+
         Ok(())
     }
 }
 
+/// Decodes each receipt's logs, validates them against the stored bloom, and writes the
+/// resulting [`RethReceipt`] into the `Receipts` table at `db_path`, keyed by the receipt's
+/// global transaction number (`BlockBodyIndices.first_tx_num + transaction_index`).
+fn persist_decoded_receipts(db_path: &str, receipts: &[Receipt]) -> Result<u64> {
+    let env: Env<WriteMap> = Env::open(Path::new(db_path), EnvKind::RW)?;
+    let read_tx = env.tx()?;
+    let write_tx = env.tx_mut()?;
+
+    let mut persisted = 0u64;
+    for receipt in receipts {
+        let (logs, bloom_valid) = receipt.decode_logs()?;
+        if !bloom_valid {
+            tracing::warn!(target: "reth::cli", tx_hash = ?receipt.tx_hash, "decoded logs bloom does not match stored bloom, skipping");
+            continue
+        }
+
+        let block_number = receipt.block_number.as_u64();
+        let body = read_tx
+            .get::<tables::BlockBodyIndices>(block_number)?
+            .ok_or_else(|| eyre::eyre!("missing body indices for block {block_number}"))?;
+        let tx_number = body.first_tx_num + receipt.transaction_index;
+
+        write_tx.put::<tables::Receipts>(tx_number, receipt.into_reth_receipt(logs))?;
+        persisted += 1;
+    }
+
+    write_tx.commit()?;
+    Ok(persisted)
+}
+
 /// ## Receipt
 ///
 /// This is a receipt types based on the [HackReceipt](https://github.com/testinprod-io/erigon/blob/pcw109550/state-import/core/types/receipt.go#L81)
@@ -154,4 +204,32 @@ impl Receipt {
         let receipts = Receipt::decode_receipt_vec(&rlp_data).map_err(|e| eyre::eyre!(e))?;
         Ok(receipts)
     }
+
+    /// Decodes this receipt's raw `logs` sub-list into structured [`Log`]s, returning whether
+    /// their recomputed bloom matches the one stored in `self.bloom`.
+    pub fn decode_logs(&self) -> Result<(Vec<Log>, bool)> {
+        let rlp = rlp::Rlp::new(&self.logs);
+
+        let mut logs = Vec::new();
+        for item in rlp.iter() {
+            let address: Address = item.val_at(0)?;
+            let topics = item.list_at(1)?;
+            let data = Bytes::from(item.val_at::<Vec<u8>>(2)?);
+            logs.push(Log { address, topics, data });
+        }
+
+        let valid = logs_bloom(logs.iter()).as_bytes() == self.bloom.as_slice();
+        Ok((logs, valid))
+    }
+
+    /// Converts this HackReceipt into the [`RethReceipt`] stored in the `Receipts` table, using
+    /// logs already decoded (and bloom-validated) by [`Receipt::decode_logs`].
+    pub fn into_reth_receipt(&self, logs: Vec<Log>) -> RethReceipt {
+        RethReceipt {
+            tx_type: TxType::try_from(self.ty).unwrap_or(TxType::Legacy),
+            success: self.status == 1,
+            cumulative_gas_used: self.cumulative_gas_used,
+            logs,
+        }
+    }
 }
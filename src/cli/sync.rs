@@ -1,8 +1,11 @@
 use crate::{
-    cli::dirs::{HeadersDbPath, StateDbPath},
+    cli::{
+        dirs::{HeadersDbPath, StateDbPath},
+        genesis::{chain_value_parser, OpChainSpec},
+    },
     database::{init_headers_db, init_state_db, split::SplitDatabase},
-    remote::digitalocean::store::DigitalOceanStore,
-    sync::{run_sync_with_snapshots, HeadersSync, StateSync, Tip},
+    remote::{DigitalOceanStore, FileStore, RemoteBackend, RemoteStore, S3Store},
+    sync::{run_sync, snapshot_database, HeadersSync, StateSync, Tip},
 };
 use clap::{crate_version, Parser, ValueEnum};
 use eyre::Context;
@@ -25,11 +28,12 @@ use reth_network::{
     error::NetworkError, FetchClient, NetworkConfig, NetworkHandle, NetworkManager,
 };
 use reth_network_api::NetworkInfo;
-use reth_primitives::{BlockHashOrNumber, ChainSpec, Head, H256};
+use reth_primitives::{BlockHashOrNumber, Head, SealedHeader, H256};
 use reth_provider::{BlockProvider, HeaderProvider, ShareableDatabase};
-use reth_staged_sync::{utils::chainspec::genesis_value_parser, Config};
+use reth_staged_sync::Config;
 use reth_tasks::TaskExecutor;
-use std::{path::PathBuf, sync::Arc};
+use reth_trie::StateRoot;
+use std::{path::PathBuf, sync::Arc, time::Duration};
 use tracing::*;
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, ValueEnum)]
@@ -38,6 +42,36 @@ enum SyncEnum {
     State,
 }
 
+/// Maximum number of ancestor headers to walk backward from the tip while verifying that it
+/// chains back to a trusted `--checkpoint`, bounding the pre-flight network cost.
+const MAX_CHECKPOINT_WALK: u64 = 10_000;
+
+/// A weak-subjectivity checkpoint: a trusted block hash, and optionally the state root it
+/// committed to, that the downloaded tip and synced state are verified against before the node
+/// trusts the remote snapshot.
+#[derive(Debug, Clone, Copy)]
+struct Checkpoint {
+    hash: H256,
+    state_root: Option<H256>,
+}
+
+/// Parses `--checkpoint <hash>[:<state_root>]`.
+fn checkpoint_value_parser(s: &str) -> eyre::Result<Checkpoint> {
+    let mut parts = s.splitn(2, ':');
+    let hash = parts
+        .next()
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| eyre::eyre!("missing checkpoint hash"))?
+        .parse()
+        .map_err(|e| eyre::eyre!("invalid checkpoint hash: {e}"))?;
+    let state_root = parts
+        .next()
+        .map(|s| s.parse().map_err(|e| eyre::eyre!("invalid checkpoint state root: {e}")))
+        .transpose()?;
+
+    Ok(Checkpoint { hash, state_root })
+}
+
 /// Start the node
 #[derive(Debug, Parser)]
 pub struct Command {
@@ -52,20 +86,96 @@ pub struct Command {
     #[arg(long, value_name = "PATH", verbatim_doc_comment, default_value_t)]
     state_db: PlatformPath<StateDbPath>,
 
+    /// The chain to run, either a named chain, a plain `ChainSpec` JSON file, or the path to an
+    /// Optimism genesis file (containing `bedrockBlock`, `optimism.eip1559Elasticity`, etc).
     #[arg(
         long,
         value_name = "CHAIN_OR_PATH",
         verbatim_doc_comment,
         default_value = "mainnet",
-        value_parser = genesis_value_parser
+        value_parser = chain_value_parser
     )]
-    chain: ChainSpec,
+    chain: OpChainSpec,
 
     #[clap(flatten)]
     network: NetworkArgs,
 
     #[arg(long = "debug.tip", help_heading = "Debug")]
     tip: H256,
+
+    /// A trusted weak-subjectivity checkpoint, `<hash>[:<state_root>]`. When set, the tip
+    /// downloaded from the network must chain back to `hash`, and (if a state root is given)
+    /// the state synced from the remote snapshot must commit to it.
+    #[arg(
+        long = "checkpoint",
+        value_name = "HASH[:STATE_ROOT]",
+        verbatim_doc_comment,
+        help_heading = "Checkpoint",
+        value_parser = checkpoint_value_parser
+    )]
+    checkpoint: Option<Checkpoint>,
+
+    /// The finalized block number backing `--checkpoint`, if known
+    #[arg(long = "checkpoint.finalized-block", value_name = "BLOCK", help_heading = "Checkpoint")]
+    checkpoint_finalized_block: Option<u64>,
+
+    /// Maximum number of attempts to fetch the tip header before giving up
+    #[arg(
+        long = "checkpoint.max-retries",
+        value_name = "COUNT",
+        help_heading = "Checkpoint",
+        default_value_t = 10
+    )]
+    checkpoint_max_retries: u32,
+
+    /// Backoff between tip fetch retries, in milliseconds
+    #[arg(
+        long = "checkpoint.retry-backoff",
+        value_name = "MILLISECONDS",
+        help_heading = "Checkpoint",
+        default_value_t = 1_000
+    )]
+    checkpoint_retry_backoff_ms: u64,
+
+    /// Which snapshot backend to pull headers/state from
+    #[arg(long = "remote", value_name = "BACKEND", help_heading = "Remote", default_value = "do")]
+    remote: RemoteBackend,
+
+    /// DigitalOcean Spaces region (only used with `--remote do`)
+    #[arg(
+        long = "remote.do-region",
+        value_name = "REGION",
+        help_heading = "Remote",
+        default_value = "fra1"
+    )]
+    remote_do_region: String,
+
+    /// DigitalOcean Spaces bucket (only used with `--remote do`)
+    #[arg(
+        long = "remote.do-bucket",
+        value_name = "BUCKET",
+        help_heading = "Remote",
+        default_value = "reth-state-snapshots"
+    )]
+    remote_do_bucket: String,
+
+    /// S3-compatible endpoint URL; omit to use AWS's default regional endpoint (only used with
+    /// `--remote s3`)
+    #[arg(long = "remote.s3-endpoint", value_name = "URL", help_heading = "Remote")]
+    remote_s3_endpoint: Option<String>,
+
+    /// S3-compatible region (only used with `--remote s3`)
+    #[arg(long = "remote.s3-region", value_name = "REGION", help_heading = "Remote")]
+    remote_s3_region: Option<String>,
+
+    /// S3-compatible bucket (only used with `--remote s3`)
+    #[arg(long = "remote.s3-bucket", value_name = "BUCKET", help_heading = "Remote")]
+    remote_s3_bucket: Option<String>,
+
+    /// A local directory, or `file://`/`http(s)://` URL, to read a pre-downloaded snapshot from
+    /// (only used with `--remote file`)
+    #[arg(long = "remote.file-path", value_name = "PATH", help_heading = "Remote")]
+    remote_file_path: Option<String>,
 }
 
 impl Command {
@@ -79,15 +189,15 @@ impl Command {
         let mut config: Config = self.load_config()?;
         info!(target: "reth::cli", path = %self.config, "Configuration loaded");
 
-        let remote =
-            DigitalOceanStore::new("fra1".to_owned(), "reth-state-snapshots".to_owned()).await;
+        let remote = self.build_remote_store().await?;
 
         info!(target: "reth::cli", headers_db = %self.headers_db, "Opening split database");
-        let headers_db = init_headers_db(&self.headers_db, &remote, self.chain.clone()).await?;
+        let headers_db =
+            init_headers_db(&self.headers_db, &remote, self.chain.inner.clone()).await?;
         info!(target: "reth::cli", "Split database opened");
 
         let (consensus, _forkchoice_state_tx) =
-            BeaconConsensus::builder().build(self.chain.clone());
+            BeaconConsensus::builder().build(self.chain.inner.clone());
         info!(target: "reth::cli", "Consensus engine initialized");
 
         self.init_trusted_nodes(&mut config);
@@ -104,33 +214,49 @@ impl Command {
         ));
 
         let fetch_client = network.fetch_client().await?;
-        let tip = Tip::new(self.tip, self.fetch_tip(fetch_client.clone(), self.tip).await?);
 
-        let db = SplitDatabase::new(
-            &self.headers_db,
-            headers_db,
-            &self.state_db,
-            init_state_db(&self.state_db, &remote, self.chain.clone(), tip).await?,
-        );
+        let retry_backoff = Duration::from_millis(self.checkpoint_retry_backoff_ms);
+        let tip_header = self
+            .fetch_tip(fetch_client.clone(), self.tip, self.checkpoint_max_retries, retry_backoff)
+            .await?;
+        let tip = Tip::new(self.tip, tip_header.number);
+
+        if let Some(checkpoint) = &self.checkpoint {
+            info!(target: "reth::cli", ?checkpoint, finalized_block = ?self.checkpoint_finalized_block, "Verifying tip chains back to trusted checkpoint");
+            self.verify_checkpoint_chain(fetch_client.clone(), tip_header, checkpoint).await?;
+            info!(target: "reth::cli", "Checkpoint verified");
+        }
+
+        let state_db = init_state_db(&self.state_db, &remote, tip).await?;
+
+        let db = SplitDatabase::new(&self.headers_db, headers_db, &self.state_db, state_db);
 
         let fetch_client = Arc::new(fetch_client);
         let header_downloader = ReverseHeadersDownloaderBuilder::from(config.stages.headers)
             .build(fetch_client.clone(), consensus.clone())
             .into_task_with(&ctx.task_executor);
         let body_downloader = BodiesDownloaderBuilder::from(config.stages.bodies)
-            .build(fetch_client.clone(), consensus.clone(), db.headers())
+            .build(
+                fetch_client.clone(),
+                consensus.clone(),
+                ShareableDatabase::new(db.headers(), self.chain.inner.clone()),
+            )
             .into_task_with(&ctx.task_executor);
 
         let headers_sync = HeadersSync::new(db.headers(), header_downloader);
 
-        let state_sync =
-            StateSync::new(db.state(), db.headers(), body_downloader, Arc::new(self.chain.clone()));
+        let state_sync = StateSync::new(
+            db.state(),
+            db.headers(),
+            body_downloader,
+            Arc::new(self.chain.inner.clone()),
+        );
 
         // Run sync
         let (rx, tx) = tokio::sync::oneshot::channel();
         info!(target: "reth::cli", "Starting state sync");
         ctx.task_executor.spawn_critical_blocking("state sync task", async move {
-            let res = run_sync_with_snapshots(headers_sync, state_sync, tip, remote, db).await;
+            let res = run_sync(headers_sync, state_sync, tip).await;
             let _ = rx.send(res);
         });
 
@@ -138,9 +264,52 @@ impl Command {
 
         info!(target: "reth::cli", "State sync has finished.");
 
+        // Only publish the synced result to `remote` once we've verified it's trustworthy: a
+        // state root mismatch must fail *before* anything is uploaded, or every other operator
+        // restoring from this remote would inherit the bad snapshot.
+        if let Some(Checkpoint { state_root: Some(expected_state_root), .. }) = self.checkpoint {
+            info!(target: "reth::cli", ?expected_state_root, "Verifying synced state root against checkpoint");
+            verify_state_root(&db.state(), expected_state_root)?;
+            info!(target: "reth::cli", "State root matches checkpoint");
+        }
+
+        info!(target: "reth::cli", "Snapshotting split database to remote store");
+        snapshot_database(remote.as_ref(), &db).await?;
+
         Ok(())
     }
 
+    /// Builds the [`RemoteStore`] selected by `--remote`, validating that the settings the chosen
+    /// backend needs were actually supplied.
+    async fn build_remote_store(&self) -> eyre::Result<Arc<dyn RemoteStore>> {
+        let store: Arc<dyn RemoteStore> = match self.remote {
+            RemoteBackend::Do => Arc::new(
+                DigitalOceanStore::new(self.remote_do_region.clone(), self.remote_do_bucket.clone())
+                    .await,
+            ),
+            RemoteBackend::S3 => {
+                let region = self
+                    .remote_s3_region
+                    .clone()
+                    .ok_or_else(|| eyre::eyre!("--remote s3 requires --remote.s3-region"))?;
+                let bucket = self
+                    .remote_s3_bucket
+                    .clone()
+                    .ok_or_else(|| eyre::eyre!("--remote s3 requires --remote.s3-bucket"))?;
+                Arc::new(S3Store::new(self.remote_s3_endpoint.clone(), region, bucket).await)
+            }
+            RemoteBackend::File => {
+                let location = self
+                    .remote_file_path
+                    .as_deref()
+                    .ok_or_else(|| eyre::eyre!("--remote file requires --remote.file-path"))?;
+                Arc::new(FileStore::new(location)?)
+            }
+        };
+
+        Ok(store)
+    }
+
     fn load_config(&self) -> eyre::Result<Config> {
         confy::load_path::<Config>(&self.config).wrap_err("Could not load config")
     }
@@ -189,36 +358,109 @@ impl Command {
     ) -> NetworkConfig<ShareableDatabase<Arc<Env<WriteMap>>>> {
         let head = Head {
             number: 0,
-            hash: self.chain.genesis_hash(),
-            timestamp: self.chain.genesis.timestamp,
-            difficulty: self.chain.genesis.difficulty,
-            total_difficulty: self.chain.genesis.difficulty,
+            hash: self.chain.inner.genesis_hash(),
+            timestamp: self.chain.inner.genesis.timestamp,
+            difficulty: self.chain.inner.genesis.difficulty,
+            total_difficulty: self.chain.inner.genesis.difficulty,
         };
         self.network
-            .network_config(config, self.chain.clone())
+            .network_config(config, self.chain.inner.clone())
             .with_task_executor(Box::new(executor))
             .set_head(head)
-            .build(ShareableDatabase::new(db, self.chain.clone()))
+            .build(ShareableDatabase::new(db, self.chain.inner.clone()))
     }
 
+    /// Fetches the header for `tip`, making at most `max_retries` attempts in total (at least
+    /// one) with `backoff` between them. Unlike the unbounded retry loop this replaces, a network that never serves the
+    /// tip now fails the command instead of hanging it forever.
     async fn fetch_tip(
         &self,
         fetch_client: FetchClient,
         tip: H256,
-    ) -> Result<u64, reth_interfaces::Error> {
-        info!(target: "reth::cli", ?tip, "Fetching tip block number from the network.");
+        max_retries: u32,
+        backoff: Duration,
+    ) -> eyre::Result<SealedHeader> {
+        info!(target: "reth::cli", ?tip, max_retries, "Fetching tip header from the network.");
+        let mut attempt = 0;
         loop {
+            attempt += 1;
             match get_single_header(fetch_client.clone(), BlockHashOrNumber::Hash(tip)).await {
                 Ok(tip_header) => {
-                    info!(target: "reth::cli", ?tip, number = tip_header.number, "Successfully fetched tip block number");
-                    return Ok(tip_header.number)
+                    info!(target: "reth::cli", ?tip, number = tip_header.number, "Successfully fetched tip header");
+                    return Ok(tip_header)
+                }
+                Err(error) if attempt < max_retries => {
+                    warn!(target: "reth::cli", %error, attempt, max_retries, "Failed to fetch the tip, retrying...");
+                    tokio::time::sleep(backoff).await;
                 }
                 Err(error) => {
-                    error!(target: "reth::cli", %error, "Failed to fetch the tip. Retrying...");
+                    return Err(error)
+                        .wrap_err_with(|| format!("failed to fetch tip {tip:?} after {attempt} attempts"))
                 }
             }
         }
     }
+
+    /// Walks back from `tip_header` through its ancestors, fetched one at a time from
+    /// `fetch_client`, until it finds `checkpoint.hash` or exceeds [`MAX_CHECKPOINT_WALK`]. Fails
+    /// closed: a tip that doesn't demonstrably chain back to the checkpoint is rejected rather
+    /// than trusted.
+    async fn verify_checkpoint_chain(
+        &self,
+        fetch_client: FetchClient,
+        tip_header: SealedHeader,
+        checkpoint: &Checkpoint,
+    ) -> eyre::Result<()> {
+        if tip_header.hash() == checkpoint.hash {
+            return Ok(())
+        }
+
+        let mut current = tip_header;
+        for _ in 0..MAX_CHECKPOINT_WALK {
+            if current.number == 0 {
+                break
+            }
+
+            let parent = get_single_header(
+                fetch_client.clone(),
+                BlockHashOrNumber::Hash(current.parent_hash),
+            )
+            .await
+            .wrap_err_with(|| {
+                format!("failed to fetch ancestor {:?} while verifying checkpoint", current.parent_hash)
+            })?;
+
+            if parent.hash() == checkpoint.hash {
+                return Ok(())
+            }
+
+            current = parent;
+        }
+
+        Err(eyre::eyre!(
+            "tip does not chain back to checkpoint {:?} within {MAX_CHECKPOINT_WALK} ancestors",
+            checkpoint.hash
+        ))
+    }
+}
+
+/// Recomputes the state root committed to by `state_db` and errors unless it matches
+/// `expected_state_root`, refusing to finalize a split database built from an untrusted or
+/// corrupted snapshot.
+fn verify_state_root(
+    state_db: &Arc<Env<WriteMap>>,
+    expected_state_root: H256,
+) -> eyre::Result<()> {
+    let tx = state_db.tx()?;
+    let computed_state_root = StateRoot::new(&tx).root().wrap_err("failed to compute state root")?;
+
+    if computed_state_root != expected_state_root {
+        return Err(eyre::eyre!(
+            "state root mismatch: computed {computed_state_root:?}, checkpoint expects {expected_state_root:?}"
+        ))
+    }
+
+    Ok(())
 }
 
 /// Drives the [NetworkManager] future until a [Shutdown](reth_tasks::shutdown::Shutdown) signal is
@@ -0,0 +1,96 @@
+//! Opens the MDBX environments `node::Command` syncs into, restoring a snapshot from a
+//! [`RemoteStore`](crate::remote::RemoteStore) first if the on-disk directory is empty.
+
+pub mod split;
+
+use crate::{remote::RemoteStore, sync::Tip};
+use reth_db::{
+    mdbx::{Env, EnvKind, WriteMap},
+    tables,
+    transaction::DbTx,
+};
+use reth_primitives::ChainSpec;
+use std::{fmt::Display, path::Path, sync::Arc};
+
+/// Opens (creating if needed) the MDBX environment backing the headers database at `path`. If
+/// the directory is empty, a headers snapshot is restored from `remote` first, so a fresh node
+/// doesn't have to re-download the entire header chain from peers. Either way, the result is
+/// checked against `chain`'s genesis before it's handed back, so a snapshot restored for the
+/// wrong network is rejected instead of silently synced on top of.
+pub async fn init_headers_db(
+    path: impl Display,
+    remote: &Arc<dyn RemoteStore>,
+    chain: ChainSpec,
+) -> eyre::Result<Arc<Env<WriteMap>>> {
+    let env = open_or_restore(Path::new(&path.to_string()), remote, "headers").await?;
+    verify_genesis(&env, &chain)?;
+    Ok(env)
+}
+
+/// Opens (creating if needed) the MDBX environment backing the state database at `path`. If the
+/// directory is empty, a state snapshot is restored from `remote` first.
+pub async fn init_state_db(
+    path: impl Display,
+    remote: &Arc<dyn RemoteStore>,
+    tip: Tip,
+) -> eyre::Result<Arc<Env<WriteMap>>> {
+    tracing::debug!(target: "reth::cli", tip_number = tip.number, "restoring state database");
+    open_or_restore(Path::new(&path.to_string()), remote, "state").await
+}
+
+/// Confirms a restored headers environment actually belongs to `chain`: if it has a block 0
+/// recorded at all, `chain`'s genesis hash must be the one recorded there. A freshly created,
+/// still-empty environment has nothing to check against and passes trivially.
+fn verify_genesis(env: &Env<WriteMap>, chain: &ChainSpec) -> eyre::Result<()> {
+    let tx = env.tx()?;
+    let genesis_hash = chain.genesis_hash();
+
+    match tx.get::<tables::HeaderNumbers>(genesis_hash)? {
+        Some(0) => Ok(()),
+        Some(other) => Err(eyre::eyre!(
+            "restored headers snapshot has configured genesis hash {genesis_hash:?} at block {other}, not block 0, is this the wrong chain?"
+        )),
+        None if tx.get::<tables::Headers>(0)?.is_some() => Err(eyre::eyre!(
+            "restored headers snapshot's block 0 header does not match configured genesis hash {genesis_hash:?}, is this the wrong chain?"
+        )),
+        None => Ok(()),
+    }
+}
+
+async fn open_or_restore(
+    path: &Path,
+    remote: &Arc<dyn RemoteStore>,
+    prefix: &str,
+) -> eyre::Result<Arc<Env<WriteMap>>> {
+    let is_empty = !path.exists() || path.read_dir()?.next().is_none();
+    if is_empty {
+        tokio::fs::create_dir_all(path).await?;
+        restore_snapshot(path, remote.as_ref(), prefix).await?;
+    }
+
+    let env: Env<WriteMap> = Env::open(path, EnvKind::RW)?;
+    env.create_tables()?;
+    Ok(Arc::new(env))
+}
+
+/// Downloads every object under `prefix` from `remote` into `dir`, recreating the snapshot's
+/// directory structure.
+async fn restore_snapshot(dir: &Path, remote: &dyn RemoteStore, prefix: &str) -> eyre::Result<()> {
+    let keys = remote.list(prefix).await?;
+    if keys.is_empty() {
+        tracing::warn!(target: "reth::cli", prefix, "remote has no snapshot to restore, starting from an empty database");
+        return Ok(())
+    }
+
+    for key in keys {
+        let relative = key.strip_prefix(prefix).unwrap_or(&key).trim_start_matches('/');
+        let path = dir.join(relative);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let bytes = remote.get(&key).await?;
+        tokio::fs::write(&path, bytes).await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use reth_db::mdbx::{Env, WriteMap};
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Bundles the two independent MDBX environments `node::Command` syncs into: one holding headers
+/// (and whatever the P2P network handler needs to answer peers' header/body requests), the other
+/// holding block bodies and the state built from them. Kept separate so a headers-only run
+/// doesn't also have to carry the much larger state snapshot, and so each can be restored from
+/// (or snapshotted to) `RemoteStore` independently.
+#[derive(Debug, Clone)]
+pub struct SplitDatabase {
+    headers_path: PathBuf,
+    headers: Arc<Env<WriteMap>>,
+    state_path: PathBuf,
+    state: Arc<Env<WriteMap>>,
+}
+
+impl SplitDatabase {
+    /// Wraps the already-opened `headers`/`state` environments, keeping their on-disk paths
+    /// around so the finished sync can be snapshotted back out.
+    pub fn new(
+        headers_path: impl Display,
+        headers: Arc<Env<WriteMap>>,
+        state_path: impl Display,
+        state: Arc<Env<WriteMap>>,
+    ) -> Self {
+        Self {
+            headers_path: PathBuf::from(headers_path.to_string()),
+            headers,
+            state_path: PathBuf::from(state_path.to_string()),
+            state,
+        }
+    }
+
+    /// The headers environment: `Headers`, `HeaderNumbers`, and everything the P2P network
+    /// handler needs to answer peers' requests.
+    pub fn headers(&self) -> Arc<Env<WriteMap>> {
+        self.headers.clone()
+    }
+
+    /// The state environment: block bodies, transactions, and the account/storage state derived
+    /// from them.
+    pub fn state(&self) -> Arc<Env<WriteMap>> {
+        self.state.clone()
+    }
+
+    /// On-disk directory backing the headers environment.
+    pub fn headers_path(&self) -> &Path {
+        &self.headers_path
+    }
+
+    /// On-disk directory backing the state environment.
+    pub fn state_path(&self) -> &Path {
+        &self.state_path
+    }
+}
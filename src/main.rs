@@ -1,8 +1,8 @@
 pub mod cli;
 // pub mod compression;
-// pub mod database;
-// pub mod remote;
-// pub mod sync;
+pub mod database;
+pub mod remote;
+pub mod sync;
 
 fn main() {
     if let Err(err) = cli::run() {
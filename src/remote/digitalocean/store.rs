@@ -0,0 +1,36 @@
+use crate::remote::{RemoteStore, S3Store};
+use async_trait::async_trait;
+
+/// DigitalOcean Spaces, the snapshot store reth's own CI publishes headers/state exports to.
+/// Spaces speaks the S3 API, so this is a thin [`S3Store`] wrapper pinned to DO's
+/// `<region>.digitaloceanspaces.com` endpoint convention.
+pub struct DigitalOceanStore {
+    inner: S3Store,
+}
+
+impl DigitalOceanStore {
+    /// Connects to the `region` Spaces datacenter (e.g. `"fra1"`) and targets `bucket`.
+    pub async fn new(region: String, bucket: String) -> Self {
+        let endpoint = format!("https://{region}.digitaloceanspaces.com");
+        Self { inner: S3Store::new(Some(endpoint), region, bucket).await }
+    }
+}
+
+#[async_trait]
+impl RemoteStore for DigitalOceanStore {
+    async fn get(&self, key: &str) -> eyre::Result<Vec<u8>> {
+        self.inner.get(key).await
+    }
+
+    async fn put(&self, key: &str, value: Vec<u8>) -> eyre::Result<()> {
+        self.inner.put(key, value).await
+    }
+
+    async fn list(&self, prefix: &str) -> eyre::Result<Vec<String>> {
+        self.inner.list(prefix).await
+    }
+
+    async fn exists(&self, key: &str) -> eyre::Result<bool> {
+        self.inner.exists(key).await
+    }
+}
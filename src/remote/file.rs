@@ -0,0 +1,134 @@
+use super::RemoteStore;
+use async_trait::async_trait;
+use eyre::{eyre, Context};
+use std::path::{Component, Path, PathBuf};
+
+/// A [`RemoteStore`] backed by a local directory, or a `file://`/`http(s)://` base URL, for
+/// seeding headers/state from a pre-downloaded snapshot when neither DigitalOcean nor a
+/// self-hosted S3 bucket is reachable (air-gapped restores, CI fixtures, etc). The HTTP variant
+/// is read-only: it can't serve `put`/`list`.
+pub enum FileStore {
+    /// Reads and writes keys as paths relative to a local directory.
+    Local(PathBuf),
+    /// Reads keys as `GET` requests against a base URL.
+    Http(reqwest::Url),
+}
+
+impl FileStore {
+    /// Builds a store from `location`: a local directory path, or a `file://`/`http(s)://` URL.
+    pub fn new(location: &str) -> eyre::Result<Self> {
+        if let Some(path) = location.strip_prefix("file://") {
+            return Ok(Self::Local(PathBuf::from(path)))
+        }
+
+        if location.starts_with("http://") || location.starts_with("https://") {
+            return Ok(Self::Http(location.parse().wrap_err("invalid snapshot URL")?))
+        }
+
+        Ok(Self::Local(PathBuf::from(location)))
+    }
+}
+
+/// Joins `key` onto `dir`, rejecting absolute paths and `..` segments so a key like
+/// `/etc/passwd` or `../../secrets` can't escape the configured snapshot directory.
+fn resolve(dir: &Path, key: &str) -> eyre::Result<PathBuf> {
+    let key_path = Path::new(key);
+    if key_path.is_absolute() || key_path.components().any(|c| c == Component::ParentDir) {
+        return Err(eyre!("snapshot key {key:?} must be a relative path with no `..` segments"))
+    }
+
+    Ok(dir.join(key_path))
+}
+
+#[async_trait]
+impl RemoteStore for FileStore {
+    async fn get(&self, key: &str) -> eyre::Result<Vec<u8>> {
+        match self {
+            Self::Local(dir) => tokio::fs::read(resolve(dir, key)?)
+                .await
+                .wrap_err_with(|| format!("failed to read {key} from {}", dir.display())),
+            Self::Http(base) => {
+                let url = base.join(key)?;
+                let response = reqwest::get(url.clone())
+                    .await
+                    .wrap_err_with(|| format!("failed to fetch {url}"))?
+                    .error_for_status()
+                    .wrap_err_with(|| format!("snapshot server rejected {url}"))?;
+                Ok(response.bytes().await?.to_vec())
+            }
+        }
+    }
+
+    async fn put(&self, key: &str, value: Vec<u8>) -> eyre::Result<()> {
+        match self {
+            Self::Local(dir) => {
+                let path = resolve(dir, key)?;
+                if let Some(parent) = path.parent() {
+                    tokio::fs::create_dir_all(parent).await?;
+                }
+                tokio::fs::write(&path, value)
+                    .await
+                    .wrap_err_with(|| format!("failed to write {}", path.display()))
+            }
+            Self::Http(_) => Err(eyre!("the http file store is read-only, it has nowhere to put {key}")),
+        }
+    }
+
+    async fn list(&self, prefix: &str) -> eyre::Result<Vec<String>> {
+        match self {
+            Self::Local(dir) => {
+                let mut keys = Vec::new();
+                let mut pending = vec![prefix.trim_end_matches('/').to_owned()];
+                while let Some(current) = pending.pop() {
+                    let mut entries = tokio::fs::read_dir(resolve(dir, &current)?)
+                        .await
+                        .wrap_err_with(|| {
+                            format!("failed to list {current} under {}", dir.display())
+                        })?;
+                    while let Some(entry) = entries.next_entry().await? {
+                        if let Some(name) = entry.file_name().to_str() {
+                            let key = format!("{current}/{name}");
+                            if entry.file_type().await?.is_dir() {
+                                pending.push(key);
+                            } else {
+                                keys.push(key);
+                            }
+                        }
+                    }
+                }
+                Ok(keys)
+            }
+            Self::Http(_) => Err(eyre!("the http file store doesn't support listing, pass exact keys")),
+        }
+    }
+
+    async fn exists(&self, key: &str) -> eyre::Result<bool> {
+        match self {
+            Self::Local(dir) => Ok(tokio::fs::try_exists(resolve(dir, key)?).await?),
+            Self::Http(base) => {
+                let url = base.join(key)?;
+                Ok(reqwest::Client::new().head(url).send().await?.status().is_success())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::resolve;
+    use std::path::Path;
+
+    #[test]
+    fn rejects_absolute_and_parent_dir_keys() {
+        let dir = Path::new("/var/snapshots");
+        assert!(resolve(dir, "/etc/passwd").is_err());
+        assert!(resolve(dir, "../../secrets").is_err());
+        assert!(resolve(dir, "nested/../../secrets").is_err());
+    }
+
+    #[test]
+    fn joins_relative_keys_onto_dir() {
+        let dir = Path::new("/var/snapshots");
+        assert_eq!(resolve(dir, "headers/000.mdbx").unwrap(), dir.join("headers/000.mdbx"));
+    }
+}
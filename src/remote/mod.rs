@@ -0,0 +1,45 @@
+//! Pluggable backends for fetching (and, for snapshot publishing, writing) the header/state
+//! snapshots that [`crate::cli::sync`] restores from.
+
+pub mod digitalocean;
+pub mod file;
+pub mod s3;
+
+use async_trait::async_trait;
+use clap::ValueEnum;
+
+pub use digitalocean::store::DigitalOceanStore;
+pub use file::FileStore;
+pub use s3::S3Store;
+
+/// A key-value object store, keyed by path-like names, that header/state snapshots are read
+/// from and published to. Every backend
+/// in this module (DigitalOcean Spaces, a generic S3-compatible endpoint, or a local
+/// filesystem/HTTP directory) implements this the same way, so `node::Command` can select one at
+/// runtime with `--remote` instead of hardcoding a concrete client.
+#[async_trait]
+pub trait RemoteStore: Send + Sync {
+    /// Fetches the object stored at `key`.
+    async fn get(&self, key: &str) -> eyre::Result<Vec<u8>>;
+
+    /// Writes `value` to `key`, creating it or overwriting an existing object.
+    async fn put(&self, key: &str, value: Vec<u8>) -> eyre::Result<()>;
+
+    /// Lists every key stored under `prefix`.
+    async fn list(&self, prefix: &str) -> eyre::Result<Vec<String>>;
+
+    /// Returns whether `key` exists in the store.
+    async fn exists(&self, key: &str) -> eyre::Result<bool>;
+}
+
+/// Selects which [`RemoteStore`] implementation `--remote` wires up.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, ValueEnum)]
+pub enum RemoteBackend {
+    /// DigitalOcean Spaces, the snapshot store reth's own CI publishes to.
+    Do,
+    /// Any S3-compatible object store, configured by endpoint/region/bucket.
+    S3,
+    /// A local directory or `file://`/`http(s)://` URL, for air-gapped restores from a
+    /// pre-downloaded snapshot.
+    File,
+}
@@ -0,0 +1,91 @@
+use super::RemoteStore;
+use async_trait::async_trait;
+use aws_sdk_s3::{config::Region, Client};
+use eyre::Context;
+
+/// A generic S3-compatible [`RemoteStore`], configured by an explicit `endpoint` (so it also
+/// covers DigitalOcean Spaces, MinIO, Cloudflare R2, etc), `region`, and `bucket`. Credentials
+/// are resolved the usual AWS SDK way (environment, shared profile, or instance metadata).
+pub struct S3Store {
+    client: Client,
+    bucket: String,
+}
+
+impl S3Store {
+    /// Builds a client against `endpoint` (`None` uses AWS's default regional endpoint) in
+    /// `region`, targeting `bucket`.
+    pub async fn new(endpoint: Option<String>, region: String, bucket: String) -> Self {
+        let mut config_loader = aws_config::from_env().region(Region::new(region));
+        if let Some(endpoint) = endpoint {
+            config_loader = config_loader.endpoint_url(endpoint);
+        }
+        let config = config_loader.load().await;
+        Self { client: Client::new(&config), bucket }
+    }
+}
+
+#[async_trait]
+impl RemoteStore for S3Store {
+    async fn get(&self, key: &str) -> eyre::Result<Vec<u8>> {
+        let object = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+            .wrap_err_with(|| format!("failed to get s3://{}/{key}", self.bucket))?;
+        let body = object.body.collect().await.wrap_err("failed to read s3 object body")?;
+        Ok(body.into_bytes().to_vec())
+    }
+
+    async fn put(&self, key: &str, value: Vec<u8>) -> eyre::Result<()> {
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .body(value.into())
+            .send()
+            .await
+            .wrap_err_with(|| format!("failed to put s3://{}/{key}", self.bucket))?;
+        Ok(())
+    }
+
+    async fn list(&self, prefix: &str) -> eyre::Result<Vec<String>> {
+        let mut keys = Vec::new();
+        let mut continuation_token = None;
+
+        loop {
+            let mut request =
+                self.client.list_objects_v2().bucket(&self.bucket).prefix(prefix);
+            if let Some(token) = continuation_token {
+                request = request.continuation_token(token);
+            }
+
+            let output = request
+                .send()
+                .await
+                .wrap_err_with(|| format!("failed to list s3://{}/{prefix}", self.bucket))?;
+            keys.extend(output.contents().iter().filter_map(|object| object.key().map(str::to_owned)));
+
+            if !output.is_truncated() {
+                break
+            }
+            continuation_token = output.next_continuation_token().map(str::to_owned);
+        }
+
+        Ok(keys)
+    }
+
+    async fn exists(&self, key: &str) -> eyre::Result<bool> {
+        match self.client.head_object().bucket(&self.bucket).key(key).send().await {
+            Ok(_) => Ok(true),
+            Err(error) if error.as_service_error().map(|e| e.is_not_found()).unwrap_or(false) => {
+                Ok(false)
+            }
+            Err(error) => {
+                Err(error).wrap_err_with(|| format!("failed to check s3://{}/{key}", self.bucket))
+            }
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use crate::sync::Tip;
+use futures::{Stream, StreamExt};
+use reth_db::{
+    database::Database as RethDatabase,
+    mdbx::{Env, WriteMap},
+    tables,
+    transaction::DbTxMut,
+};
+use reth_primitives::SealedHeader;
+use std::sync::Arc;
+
+/// Drives a header downloader stream to completion, persisting each batch of downloaded headers
+/// into the `Headers`/`HeaderNumbers` tables of `db` as it arrives.
+pub struct HeadersSync<Downloader> {
+    db: Arc<Env<WriteMap>>,
+    downloader: Downloader,
+}
+
+impl<Downloader> HeadersSync<Downloader>
+where
+    Downloader: Stream<Item = Vec<SealedHeader>> + Unpin,
+{
+    /// Pairs `downloader` with the headers environment it should persist into.
+    pub fn new(db: Arc<Env<WriteMap>>, downloader: Downloader) -> Self {
+        Self { db, downloader }
+    }
+
+    /// Consumes header batches from the downloader until it delivers `tip`'s header (or the
+    /// stream ends), committing every batch as it's written.
+    pub async fn run(&mut self, tip: Tip) -> eyre::Result<()> {
+        while let Some(headers) = self.downloader.next().await {
+            if headers.is_empty() {
+                continue
+            }
+
+            let tx = self.db.tx_mut()?;
+            let mut reached_tip = false;
+            for header in &headers {
+                tx.put::<tables::Headers>(header.number, header.clone().unseal())?;
+                tx.put::<tables::HeaderNumbers>(header.hash(), header.number)?;
+                reached_tip |= header.number == tip.number;
+            }
+            tx.commit()?;
+
+            tracing::debug!(
+                target: "reth::cli",
+                first = ?headers.first().map(|h| h.number),
+                last = ?headers.last().map(|h| h.number),
+                "wrote header batch"
+            );
+
+            if reached_tip {
+                break
+            }
+        }
+
+        Ok(())
+    }
+}
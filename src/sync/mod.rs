@@ -0,0 +1,68 @@
+//! Drives header/body downloaders to a target [`Tip`], persisting what they fetch into a
+//! [`SplitDatabase`](crate::database::split::SplitDatabase). Snapshotting the result to a
+//! [`RemoteStore`](crate::remote::RemoteStore) is a separate, later step ([`snapshot_database`]):
+//! the caller decides when the synced data is trustworthy enough to publish (e.g. only after a
+//! `--checkpoint` state root check passes), rather than it happening unconditionally as a side
+//! effect of syncing.
+
+pub mod headers;
+pub mod state;
+pub mod tip;
+
+pub use headers::HeadersSync;
+pub use state::StateSync;
+pub use tip::Tip;
+
+use crate::{database::split::SplitDatabase, remote::RemoteStore};
+use futures::Stream;
+use reth_primitives::{SealedBlock, SealedHeader};
+use std::path::Path;
+
+/// Runs `headers_sync` to `tip`, then `state_sync` to the same tip. Does not touch any
+/// [`RemoteStore`]; call [`snapshot_database`] afterwards once the caller is satisfied the
+/// synced result should be published.
+pub async fn run_sync<H, B>(
+    mut headers_sync: HeadersSync<H>,
+    mut state_sync: StateSync<B>,
+    tip: Tip,
+) -> eyre::Result<()>
+where
+    H: Stream<Item = Vec<SealedHeader>> + Unpin,
+    B: Stream<Item = Vec<SealedBlock>> + Unpin,
+{
+    headers_sync.run(tip).await?;
+    state_sync.run(tip).await?;
+
+    Ok(())
+}
+
+/// Uploads `db`'s headers and state directories to `remote`, so the next run (or another
+/// operator) can restore from them instead of re-downloading everything from peers.
+pub async fn snapshot_database(remote: &dyn RemoteStore, db: &SplitDatabase) -> eyre::Result<()> {
+    upload_dir(remote, "headers", db.headers_path()).await?;
+    upload_dir(remote, "state", db.state_path()).await?;
+
+    Ok(())
+}
+
+/// Recursively uploads every file under `dir` to `remote`, keyed by `<prefix>/<relative path>`.
+async fn upload_dir(remote: &dyn RemoteStore, prefix: &str, dir: &Path) -> eyre::Result<()> {
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let mut entries = tokio::fs::read_dir(&current).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if entry.file_type().await?.is_dir() {
+                pending.push(path);
+                continue
+            }
+
+            let relative = path.strip_prefix(dir).unwrap_or(&path);
+            let key = format!("{prefix}/{}", relative.display());
+            let bytes = tokio::fs::read(&path).await?;
+            remote.put(&key, bytes).await?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use crate::sync::Tip;
+use futures::{Stream, StreamExt};
+use reth_db::{
+    database::Database as RethDatabase,
+    mdbx::{Env, WriteMap},
+    models::StoredBlockBodyIndices,
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_primitives::{ChainSpec, SealedBlock};
+use std::sync::Arc;
+
+/// Drives a body downloader stream to completion, persisting each downloaded block's
+/// transactions and body indices into the state environment. `headers_db` is consulted (but
+/// never written) to reject a body whose header wasn't already written by
+/// [`HeadersSync`](super::HeadersSync).
+pub struct StateSync<Downloader> {
+    state_db: Arc<Env<WriteMap>>,
+    headers_db: Arc<Env<WriteMap>>,
+    downloader: Downloader,
+    /// OP's hardfork schedule and EIP-1559 parameters, consulted once block execution (rather
+    /// than just body persistence) lands here.
+    chain_spec: Arc<ChainSpec>,
+    next_tx_num: u64,
+}
+
+impl<Downloader> StateSync<Downloader>
+where
+    Downloader: Stream<Item = Vec<SealedBlock>> + Unpin,
+{
+    /// Pairs `downloader` with the state environment it should persist into and the headers
+    /// environment it cross-checks against.
+    pub fn new(
+        state_db: Arc<Env<WriteMap>>,
+        headers_db: Arc<Env<WriteMap>>,
+        downloader: Downloader,
+        chain_spec: Arc<ChainSpec>,
+    ) -> Self {
+        Self { state_db, headers_db, downloader, chain_spec, next_tx_num: 0 }
+    }
+
+    /// Consumes block batches from the downloader until it delivers `tip`'s block (or the
+    /// stream ends), committing every batch as it's written.
+    pub async fn run(&mut self, tip: Tip) -> eyre::Result<()> {
+        let _ = &self.chain_spec;
+
+        while let Some(blocks) = self.downloader.next().await {
+            if blocks.is_empty() {
+                continue
+            }
+
+            let headers_tx = self.headers_db.tx()?;
+            let state_tx = self.state_db.tx_mut()?;
+            let mut reached_tip = false;
+
+            for block in &blocks {
+                headers_tx.get::<tables::Headers>(block.header.number)?.ok_or_else(|| {
+                    eyre::eyre!("downloaded body for unsynced header {}", block.header.number)
+                })?;
+
+                let first_tx_num = self.next_tx_num;
+                for transaction in &block.body {
+                    state_tx.put::<tables::Transactions>(self.next_tx_num, transaction.clone())?;
+                    self.next_tx_num += 1;
+                }
+
+                state_tx.put::<tables::BlockBodyIndices>(
+                    block.header.number,
+                    StoredBlockBodyIndices { first_tx_num, tx_count: block.body.len() as u64 },
+                )?;
+
+                reached_tip |= block.header.number == tip.number;
+            }
+
+            state_tx.commit()?;
+            if reached_tip {
+                break
+            }
+        }
+
+        Ok(())
+    }
+}
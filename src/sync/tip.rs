@@ -0,0 +1,18 @@
+use reth_primitives::H256;
+
+/// The hash and resolved block number of the block sync is targeting, parsed from
+/// `--debug.tip` and the tip header fetched to resolve its number.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Tip {
+    /// The tip's block hash.
+    pub hash: H256,
+    /// The tip's block number.
+    pub number: u64,
+}
+
+impl Tip {
+    /// Pairs a tip `hash` with its resolved block `number`.
+    pub fn new(hash: H256, number: u64) -> Self {
+        Self { hash, number }
+    }
+}